@@ -1,8 +1,19 @@
+//! # `norgopolis_protos` dependency
+//!
+//! `Invoker::invoke_streaming` and `Invoker::InvokeStreamingStream` below are not implemented in
+//! this crate — they're generated from `norgopolis_protos`'s `invoker.proto`. This file only
+//! compiles against a `norgopolis_protos` release that has already added
+//! `rpc InvokeStreaming(stream Invocation) returns (stream MessagePack)` to the `Invoker` service
+//! alongside the existing `rpc Invoke`. That proto change, and the corresponding
+//! `norgopolis_protos` version bump, has to land and be published first; this commit cannot be
+//! merged standalone against the currently-published `norgopolis_protos` and needs to be
+//! coordinated with that release.
+
 use std::pin::Pin;
 
 use crate::module_communication::{invoker_server::Invoker, Invocation, MessagePack};
-use futures::Stream;
-use tonic::{Request, Response, Status};
+use futures::{Stream, StreamExt};
+use tonic::{Code, Request, Response, Status, Streaming};
 
 #[crate::async_trait]
 pub trait Service {
@@ -13,6 +24,29 @@ pub trait Service {
         fn_name: String,
         args: Option<MessagePack>,
     ) -> Result<Self::Stream, Status>;
+
+    /// Handles a client-streaming invocation. Unlike `call`, which receives a single
+    /// optional argument, `requests` yields an unbounded sequence of MessagePack frames
+    /// that the caller pushes over time (e.g. document chunks or offset acknowledgements).
+    /// The returned stream may start producing responses before the client has finished
+    /// sending, and keeps running until `requests` is exhausted.
+    ///
+    /// The response type is boxed rather than `Self::Stream` so that implementing this is
+    /// optional: modules that don't need client-streaming can rely on the default, which
+    /// rejects the call with `Unimplemented` instead of every `Service` in the ecosystem
+    /// having to hand-write that themselves.
+    async fn call_streaming(
+        &self,
+        fn_name: String,
+        requests: Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>, Status> {
+        let _ = (fn_name, requests);
+
+        Err(Status::new(
+            Code::Unimplemented,
+            "this module does not support client-streaming invocations",
+        ))
+    }
 }
 
 pub struct InvokerService<T> {
@@ -35,6 +69,7 @@ where
     T: Service + Sync + Send + 'static,
 {
     type InvokeStream = Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>;
+    type InvokeStreamingStream = Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>;
 
     async fn invoke(
         &self,
@@ -49,6 +84,65 @@ where
             .call(invocation.function_name, invocation.args)
             .await?;
 
-        Ok(Response::new(Box::pin(response)))
+        Ok(Response::new(self.keepalive_on_send(response)))
+    }
+
+    async fn invoke_streaming(
+        &self,
+        request: Request<Streaming<Invocation>>,
+    ) -> Result<Response<Self::InvokeStreamingStream>, Status> {
+        let mut inbound = request.into_inner();
+
+        let _ = self.tx.send(());
+
+        // The function name is only meaningful on the first message of the stream;
+        // every subsequent message only carries the next chunk of arguments.
+        let first = inbound
+            .message()
+            .await?
+            .ok_or_else(|| Status::new(Code::InvalidArgument, "expected at least one invocation"))?;
+        let fn_name = first.function_name;
+
+        let tx = self.tx.clone();
+        let requests = futures::stream::once(futures::future::ok(first.args))
+            .chain(inbound.map(|invocation| invocation.map(|invocation| invocation.args)))
+            .map(|args| {
+                args?.ok_or_else(|| Status::new(Code::InvalidArgument, "missing invocation args"))
+            })
+            // Every inbound chunk resets the idle timer too, not just the first message,
+            // so a long client-streamed upload isn't reaped as idle before it produces
+            // any output.
+            .inspect(move |_| {
+                let _ = tx.send(());
+            });
+
+        let response = self
+            .service
+            .call_streaming(fn_name, Box::pin(requests))
+            .await?;
+
+        Ok(Response::new(self.keepalive_on_send(response)))
+    }
+}
+
+impl<T> InvokerService<T>
+where
+    T: Service,
+{
+    /// Wraps a response stream so that every frame sent back to the client also resets
+    /// the module's idle timer, not just inbound invocations. Otherwise a module doing
+    /// long streaming work with no new invocations would be reaped as idle mid-response.
+    fn keepalive_on_send<S>(
+        &self,
+        stream: S,
+    ) -> Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>
+    where
+        S: Stream<Item = Result<MessagePack, Status>> + Send + 'static,
+    {
+        let tx = self.tx.clone();
+
+        Box::pin(stream.inspect(move |_| {
+            let _ = tx.send(());
+        }))
     }
 }
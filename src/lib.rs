@@ -138,7 +138,73 @@
 //! 
 //! First, we create a sender and receiver via tokio's `unbounded_channel()`. This allows us to send data to the client
 //! and for the client to read data from the module. All return messages have to be encoded via `MessagePack::encode`.
-//! 
+//!
+//! An unbounded channel has no limit on how many unsent messages can pile up, so a slow client can't throttle a
+//! fast producer and memory grows without bound. For functions that can generate large result sets (parsing,
+//! database scans, ...) prefer `norgopolis_module::response::ResponseSender` instead, which wraps a bounded
+//! channel: `send().await` suspends the producing task until the client has drained enough frames to make room.
+//! `ResponseSender::with_default_capacity` picks a sensible capacity for you; reach for `ResponseSender::new`
+//! if a handler needs to tune it.
+//!
+//! ```rs
+//! use norgopolis_module::response::ResponseSender;
+//!
+//! let (tx, rx) = ResponseSender::with_default_capacity();
+//!
+//! tokio::spawn(async move {
+//!     tx.send(format!("Hello, {}!", args.name)).await.ok();
+//! });
+//!
+//! Ok(rx)
+//! ```
+//!
+//! ### Skipping the Boilerplate with `Router`
+//!
+//! Hand-matching over `function.as_str()`, decoding arguments and mapping errors to status codes is the same
+//! shape for every function a module exposes. `norgopolis_module::router::Router` implements `Service` for you
+//! so you don't have to repeat it:
+//!
+//! ```rs
+//! use norgopolis_module::router::Router;
+//!
+//! let router = Router::new().handler("my-function", |args: MyParameters| async move {
+//!     Ok(format!("Hello, {}!", args.name))
+//! });
+//!
+//! Module::new().start(router).await.unwrap();
+//! ```
+//!
+//! The closure's argument type drives the `MessagePack::decode`, a decode failure is turned into an
+//! `InvalidArgument` status automatically, and an unregistered function name becomes `NotFound`. Use
+//! `Router::handler_streaming` instead when a function needs to return more than one message.
+//!
+//! ### Accepting a Stream of Requests
+//!
+//! `call` only ever receives a single, optional argument, so it's a poor fit for a client that wants to push a
+//! sequence of messages into one invocation over time (feeding a parser document chunks, for example). For that,
+//! implement `call_streaming` instead. It's handed a `Stream` of MessagePack arguments (each still to be decoded)
+//! rather than a single one, and
+//! keeps running for as long as the client keeps sending:
+//!
+//! ```rs
+//! #[norgopolis_module::async_trait]
+//! impl Service for MyModule {
+//!     type Stream = UnboundedReceiverStream<Result<MessagePack, Status>>;
+//!
+//!     async fn call(&self, function: String, args: Option<MessagePack>) -> Result<Self::Stream, Status> {
+//!         todo!()
+//!     }
+//!
+//!     async fn call_streaming(
+//!         &self,
+//!         function: String,
+//!         mut requests: Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>,
+//!     ) -> Result<Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>, Status> {
+//!         todo!()
+//!     }
+//! }
+//! ```
+//!
 //! ### Running the Module
 //! 
 //! Now that we have all of the code set up, create an asynchronous main function. In here we will instantiate our
@@ -152,36 +218,94 @@
 //!         .unwrap()
 //! }
 //! ```
-//! 
+//!
+//! ### Graceful Shutdown
+//!
+//! `Module::start` (and `serve_tcp`/`serve_unix`) already shut the module down on their own once
+//! `Module::timeout` has passed with no inbound invocation and no outbound response frame. When that
+//! happens, the module stops accepting new invocations, lets any in-flight `call`/`call_streaming`
+//! streams drain to completion for up to `Module::grace_period`, then returns from `start` normally
+//! instead of killing the process mid-response.
+//!
+//! To trigger that same shutdown from elsewhere (a signal handler, an admin RPC, ...), grab a
+//! `ModuleHandle` from the `Module` before handing it off, since `start` consumes it:
+//!
+//! ```rs
+//! let module = Module::new().grace_period(Duration::from_secs(10));
+//! let handle = module.handle();
+//!
+//! tokio::spawn(async move {
+//!     // ...decide it's time to shut down...
+//!     handle.shutdown();
+//! });
+//!
+//! module.start(MyModule::default()).await.unwrap();
+//! ```
+//!
+//! ### Calling Other Modules
+//!
+//! A module isn't limited to serving requests; it can also be a client of other modules. This lets you compose
+//! modules instead of reimplementing each other's functionality, for example a database module calling into a
+//! parser module. Use `norgopolis_module::module_client::ModuleClient` for this:
+//!
+//! ```rs
+//! use norgopolis_module::module_client::ModuleClient;
+//!
+//! let mut client = ModuleClient::connect("http://[::1]:50051").await?;
+//! let mut response = client.invoke("parser", "parse-file", "contents.norg").await?;
+//!
+//! while let Some(message) = response.next().await {
+//!     let message: String = message?.decode()?;
+//! }
+//! ```
+//!
+//! This dials the router as its own outbound connection, so it only works for a module served over
+//! `Module::serve_tcp`/`serve_unix`, where the router has a separate address to be dialed at. A module served
+//! over the default `Module::start` (stdio) has no such address to reach the router back on — see
+//! `norgopolis_module::module_client::ModuleClient`'s docs for why that case isn't supported yet. The routing
+//! convention used here (encoding the target module into `function_name`) is also this crate's best guess, not
+//! a confirmed part of the router's dispatch contract — see the same docs before relying on it.
+//!
 //! Voila! You now have a fundamental understanding of how modules communicate with Norgopolis and how to write your own
 //! norgopolis module. Happy coding!
 
 pub mod invoker_service;
+pub mod module_client;
+pub mod response;
+pub mod router;
 mod stdio_service;
+pub mod transport;
 
-use std::time::Duration;
+use std::{net::SocketAddr, path::PathBuf, time::Duration};
 
-use futures::FutureExt;
 use invoker_service::InvokerService;
 use invoker_service::Service;
 use module_communication::invoker_server::InvokerServer;
-use stdio_service::StdioService;
 use tokio::time::sleep;
-use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
 use tonic::transport::Server;
+use transport::{StdioTransport, TcpTransport, Transport, UnixTransport};
 
 pub use norgopolis_protos::module_communication;
 pub use tonic::async_trait;
 pub use tonic::{Code, Status};
 
-/// Describes a module that can communicate with Norgopolis
-/// over stdin/stdout.
+/// Describes a module that can communicate with Norgopolis. By default this happens
+/// over stdin/stdout (see [`Module::start`]), but a module can also be served over TCP
+/// or a Unix socket, or any custom [`transport::Transport`].
 pub struct Module {
     /// Timeout duration for the module. If no messages are received by the module after this time
     /// has passed the module will automatically shut down.
     ///
     /// Default is 5 minutes.
     pub timeout: Duration,
+    /// Once a graceful shutdown begins (due to [`Module::timeout`] or [`ModuleHandle::shutdown`]),
+    /// the maximum amount of time to let in-flight `call`/`call_streaming` streams drain before
+    /// giving up and returning anyway.
+    ///
+    /// Default is 30 seconds.
+    pub grace_period: Duration,
+    cancellation: CancellationToken,
 }
 
 impl Default for Module {
@@ -194,44 +318,131 @@ impl Module {
     pub fn new() -> Self {
         Module {
             timeout: Duration::from_secs(60 * 5),
+            grace_period: Duration::from_secs(30),
+            cancellation: CancellationToken::new(),
         }
     }
 
     pub fn timeout(self, timeout: Duration) -> Self {
-        Module { timeout }
+        Module { timeout, ..self }
+    }
+
+    pub fn grace_period(self, grace_period: Duration) -> Self {
+        Module { grace_period, ..self }
+    }
+
+    /// Returns a handle that can be used to trigger a graceful shutdown from outside
+    /// `start`/`serve_tcp`/`serve_unix`. Take this before handing the `Module` off to
+    /// one of those methods, since they consume it.
+    pub fn handle(&self) -> ModuleHandle {
+        ModuleHandle {
+            cancellation: self.cancellation.clone(),
+        }
     }
 
+    /// Runs the module over stdin/stdout, the default transport used when a module is
+    /// spawned and owned by a single parent process (e.g. the Norgopolis router).
     pub async fn start<S>(self, service: S) -> Result<(), anyhow::Error>
     where
         S: Service + Sync + Send + 'static,
     {
+        self.serve(service, StdioTransport).await
+    }
+
+    /// Runs the module as a standalone TCP daemon, reachable by multiple clients.
+    /// Useful for local debugging, integration tests, or out-of-process supervision.
+    pub async fn serve_tcp<S>(self, service: S, addr: SocketAddr) -> Result<(), anyhow::Error>
+    where
+        S: Service + Sync + Send + 'static,
+    {
+        self.serve(service, TcpTransport { addr }).await
+    }
+
+    /// Runs the module as a standalone daemon over a Unix domain socket, the same
+    /// multi-client use case as [`Module::serve_tcp`] without going through TCP.
+    pub async fn serve_unix<S>(
+        self,
+        service: S,
+        path: impl Into<PathBuf>,
+    ) -> Result<(), anyhow::Error>
+    where
+        S: Service + Sync + Send + 'static,
+    {
+        self.serve(service, UnixTransport { path: path.into() })
+            .await
+    }
+
+    /// Runs the module over any [`Transport`], driving the keepalive timeout and wiring
+    /// the service into the Invoker gRPC server.
+    async fn serve<S, T>(self, service: S, transport: T) -> Result<(), anyhow::Error>
+    where
+        S: Service + Sync + Send + 'static,
+        T: Transport,
+    {
+        let Module {
+            timeout,
+            grace_period,
+            cancellation,
+        } = self;
+
         let (keepalive_tx, mut keepalive_rx) = tokio::sync::mpsc::unbounded_channel::<()>();
 
-        tokio::spawn(async move {
-            sleep(self.timeout).await;
+        tokio::spawn({
+            let cancellation = cancellation.clone();
 
-            if keepalive_rx.recv().now_or_never().is_none() {
-                std::process::exit(0);
+            async move {
+                loop {
+                    tokio::select! {
+                        _ = sleep(timeout) => {
+                            // Idle for a whole `timeout` with no inbound invocation and
+                            // no outbound frame: begin a graceful shutdown instead of
+                            // killing the process outright.
+                            cancellation.cancel();
+                            return;
+                        }
+                        message = keepalive_rx.recv() => {
+                            if message.is_none() {
+                                return;
+                            }
+                            // Activity happened; loop back around and reset the timer.
+                        }
+                    }
+                }
             }
-
-            // Drain the remained of the messages.
-            while keepalive_rx.recv().now_or_never().is_some() {}
         });
 
-        let (stdin, stdout) = (tokio::io::stdin(), tokio::io::stdout());
-        let stdio_service = StdioService { stdin, stdout };
-
-        // TODO: Do this in a better way
-        // `once_stream` doesn't work :/
-        let (tx, rx) = tokio::sync::mpsc::channel::<Result<StdioService, anyhow::Error>>(1);
-        tx.send(Ok(stdio_service)).await?;
+        let incoming = transport.incoming().await?;
 
-        Ok(Server::builder()
+        let server = Server::builder()
             .add_service(InvokerServer::new(InvokerService::new(
                 service,
                 keepalive_tx,
             )))
-            .serve_with_incoming(ReceiverStream::new(rx))
-            .await?)
+            .serve_with_incoming_shutdown(incoming, cancellation.cancelled());
+        tokio::pin!(server);
+
+        tokio::select! {
+            result = &mut server => Ok(result?),
+            // Bound how long we wait for in-flight streams to drain once a graceful
+            // shutdown has actually begun, rather than hanging indefinitely.
+            _ = async { cancellation.cancelled().await; sleep(grace_period).await } => Ok(()),
+        }
+    }
+}
+
+/// A handle used to gracefully shut a running [`Module`] down, obtained via
+/// [`Module::handle`] before the module is started.
+#[derive(Clone)]
+pub struct ModuleHandle {
+    cancellation: CancellationToken,
+}
+
+impl ModuleHandle {
+    /// Begins a graceful shutdown: the module stops accepting new invocations, lets
+    /// outstanding `call`/`call_streaming` streams drain to completion for up to
+    /// [`Module::grace_period`], then `start`/`serve_tcp`/`serve_unix` returns, whether or
+    /// not those streams have finished.
+    pub fn shutdown(&self) {
+        self.cancellation.cancel();
     }
 }
@@ -0,0 +1,66 @@
+use crate::module_communication::{invoker_client::InvokerClient, Invocation, MessagePack};
+use futures::Stream;
+use serde::Serialize;
+use tonic::{transport::Channel, Request, Status};
+
+/// A client for invoking functions on other Norgopolis modules, symmetric to the
+/// [`crate::invoker_service::Service`] half every module implements to *receive*
+/// invocations. Use this when a module needs to compose with another module (e.g. a
+/// database module calling a parser module) instead of hand-rolling a tonic client.
+///
+/// # Known limitation: stdio-transported modules
+///
+/// This connects to the router as an independent outbound gRPC connection, dialed at a
+/// given address. That works for a module served over [`crate::transport::TcpTransport`]
+/// or [`crate::transport::UnixTransport`], where the router exposes some address a module
+/// process can separately dial into. It does **not** give a [`crate::transport::StdioTransport`]
+/// module (the default, and currently the only transport [`crate::Module::start`] documents)
+/// any way to call out: that module's only connection to the router is the one inbound
+/// stdio pipe it's being served over, and there is no separate address to dial back into,
+/// nor any multiplexing of outbound calls over that same pipe implemented here. Making the
+/// stdio case work would mean sharing one physical connection between the server half
+/// (already driven by `Module::start`) and this client half, which isn't something this
+/// type attempts. Until that's designed and confirmed with whoever's routing stdio modules
+/// through the router, treat `ModuleClient` as usable only for the TCP/Unix transports.
+pub struct ModuleClient {
+    client: InvokerClient<Channel>,
+}
+
+impl ModuleClient {
+    /// Connects to the Norgopolis router reachable at `endpoint`. See the type-level docs
+    /// for why this isn't usable from a module running over [`crate::transport::StdioTransport`].
+    pub async fn connect(endpoint: impl Into<String>) -> Result<Self, anyhow::Error> {
+        let client = InvokerClient::connect(endpoint.into()).await?;
+
+        Ok(ModuleClient { client })
+    }
+
+    /// Routes `function` to `target_module` via the router and returns the stream of
+    /// responses it produces. `args` is encoded the same way a module would encode a
+    /// response with `MessagePack::encode`, and is decoded on the other end with
+    /// `MessagePack::decode`, same as any other invocation.
+    ///
+    /// `Invocation` only carries a `function_name` and `args`, with no dedicated field for
+    /// a target module. Encoding the target as a `target_module/function` prefix on
+    /// `function_name` is this crate's best guess at a wire-compatible way to express
+    /// routing with the fields that exist today — it is **not** a confirmed description of
+    /// the router's actual dispatch contract, and nothing in this crate or the router's
+    /// source has verified it. Do not treat this as settled; confirm the real contract
+    /// against the router (or a future `norgopolis_protos` field meant for this) before
+    /// relying on it, and update this to match once that's known.
+    pub async fn invoke(
+        &mut self,
+        target_module: &str,
+        function: &str,
+        args: impl Serialize,
+    ) -> Result<impl Stream<Item = Result<MessagePack, Status>>, Status> {
+        let request = Request::new(Invocation {
+            function_name: format!("{target_module}/{function}"),
+            args: Some(MessagePack::encode(args)),
+        });
+
+        let response = self.client.invoke(request).await?;
+
+        Ok(response.into_inner())
+    }
+}
@@ -0,0 +1,57 @@
+use serde::Serialize;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+use tonic::Status;
+
+use crate::module_communication::MessagePack;
+
+/// The error returned by [`ResponseSender::send`]/[`ResponseSender::send_error`] when the
+/// client has disconnected and dropped its end of the channel.
+pub type SendError = mpsc::error::SendError<Result<MessagePack, Status>>;
+
+/// A bounded sender for a module's response stream. Unlike `tokio::sync::mpsc::unbounded_channel`,
+/// `send` suspends the producing task until the gRPC client has drained enough frames to make
+/// room, giving a module backpressure for free instead of growing memory without limit. This is
+/// the recommended default for functions that can produce large result sets (e.g. parsing or
+/// database scans); reach for an unbounded channel directly only if a handler genuinely must
+/// never block on send.
+pub struct ResponseSender {
+    tx: mpsc::Sender<Result<MessagePack, Status>>,
+}
+
+impl ResponseSender {
+    /// The default channel capacity used by [`ResponseSender::with_default_capacity`].
+    ///
+    /// This isn't threaded through [`crate::Module`] the way `timeout`/`grace_period` are,
+    /// because those configure the module's own lifecycle, which `Module::serve` owns end
+    /// to end — whereas a response buffer's capacity is a property of one handler's call,
+    /// created wherever that handler runs, with no `Module` in scope to read a default off
+    /// of. A constant gives every handler the same sensible default without requiring one.
+    pub const DEFAULT_CAPACITY: usize = 32;
+
+    /// Creates a bounded response channel holding at most `capacity` unsent frames, and the
+    /// stream to return as a handler's response, e.g. from `call`/`call_streaming`.
+    pub fn new(capacity: usize) -> (Self, ReceiverStream<Result<MessagePack, Status>>) {
+        let (tx, rx) = mpsc::channel(capacity);
+
+        (ResponseSender { tx }, ReceiverStream::new(rx))
+    }
+
+    /// Creates a bounded response channel using [`ResponseSender::DEFAULT_CAPACITY`], for
+    /// handlers that don't need to tune it themselves.
+    pub fn with_default_capacity() -> (Self, ReceiverStream<Result<MessagePack, Status>>) {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+
+    /// Encodes `message` and sends it to the client, suspending until there's room in the
+    /// channel.
+    pub async fn send(&self, message: impl Serialize) -> Result<(), SendError> {
+        self.tx.send(Ok(MessagePack::encode(message))).await
+    }
+
+    /// Sends an error packet for a failed portion of the work, suspending until there's
+    /// room in the channel. The call itself can keep going afterwards.
+    pub async fn send_error(&self, status: Status) -> Result<(), SendError> {
+        self.tx.send(Err(status)).await
+    }
+}
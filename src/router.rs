@@ -0,0 +1,174 @@
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use tonic::{Code, Status};
+
+use crate::{invoker_service::Service, module_communication::MessagePack};
+
+type BoxStream = Pin<Box<dyn Stream<Item = Result<MessagePack, Status>> + Send>>;
+type BoxHandlerFuture = Pin<Box<dyn Future<Output = Result<BoxStream, Status>> + Send>>;
+type HandlerFn = Box<dyn Fn(Option<MessagePack>) -> BoxHandlerFuture + Send + Sync>;
+
+/// A declarative alternative to hand-writing a `match function.as_str() { ... }` inside
+/// `Service::call`. Register one handler per function name; `Router` takes care of
+/// decoding the argument into the handler's parameter type, mapping a decode failure to
+/// `InvalidArgument`, mapping an unregistered function name to `NotFound`, and encoding
+/// whatever the handler returns. `Router` itself implements `Service`, so it can be
+/// passed straight to [`crate::Module::start`].
+#[derive(Default)]
+pub struct Router {
+    handlers: HashMap<String, HandlerFn>,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Router::default()
+    }
+
+    /// Registers a handler that decodes its argument as `P` and returns a single
+    /// response, which is automatically wrapped into a one-item stream.
+    pub fn handler<P, R, F, Fut>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        P: DeserializeOwned + Send + 'static,
+        R: Serialize + Send + 'static,
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<R, Status>> + Send + 'static,
+    {
+        let f = Arc::new(f);
+
+        self.handlers.insert(
+            name.into(),
+            Box::new(move |args| {
+                let f = Arc::clone(&f);
+
+                Box::pin(async move {
+                    let args: P = decode(args)?;
+                    let response = MessagePack::encode(f(args).await?);
+
+                    Ok(Box::pin(futures::stream::once(futures::future::ok(response))) as BoxStream)
+                })
+            }),
+        );
+
+        self
+    }
+
+    /// Registers a handler that decodes its argument as `P` and returns a stream of
+    /// responses, for functions that produce more data than comfortably fits in one
+    /// message (e.g. parsing or database scans).
+    pub fn handler_streaming<P, R, S, F, Fut>(mut self, name: impl Into<String>, f: F) -> Self
+    where
+        P: DeserializeOwned + Send + 'static,
+        R: Serialize + Send + 'static,
+        S: Stream<Item = Result<R, Status>> + Send + 'static,
+        F: Fn(P) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<S, Status>> + Send + 'static,
+    {
+        let f = Arc::new(f);
+
+        self.handlers.insert(
+            name.into(),
+            Box::new(move |args| {
+                let f = Arc::clone(&f);
+
+                Box::pin(async move {
+                    let args: P = decode(args)?;
+                    let responses = f(args).await?;
+
+                    Ok(Box::pin(responses.map(|item| item.map(MessagePack::encode))) as BoxStream)
+                })
+            }),
+        );
+
+        self
+    }
+}
+
+fn decode<P: DeserializeOwned>(args: Option<MessagePack>) -> Result<P, Status> {
+    args.ok_or_else(|| Status::new(Code::InvalidArgument, "missing arguments"))?
+        .decode()
+        .map_err(|err| Status::new(Code::InvalidArgument, err.to_string()))
+}
+
+#[crate::async_trait]
+impl Service for Router {
+    type Stream = BoxStream;
+
+    async fn call(
+        &self,
+        fn_name: String,
+        args: Option<MessagePack>,
+    ) -> Result<Self::Stream, Status> {
+        let handler = self
+            .handlers
+            .get(&fn_name)
+            .ok_or_else(|| Status::new(Code::NotFound, "Requested function not found!"))?;
+
+        handler(args).await
+    }
+
+    // `call_streaming` is intentionally not overridden: `Router` only models the
+    // unary-in/stream-out shape, and `Service`'s default rejects client-streaming
+    // invocations with `Unimplemented`.
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Params {
+        name: String,
+    }
+
+    fn greeter() -> Router {
+        Router::new().handler("greet", |params: Params| async move {
+            Ok::<_, Status>(format!("Hello, {}!", params.name))
+        })
+    }
+
+    #[tokio::test]
+    async fn unregistered_function_returns_not_found() {
+        let err = greeter()
+            .call("does-not-exist".to_string(), None)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn missing_arguments_return_invalid_argument() {
+        let err = greeter().call("greet".to_string(), None).await.unwrap_err();
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn malformed_arguments_return_invalid_argument() {
+        let err = greeter()
+            .call("greet".to_string(), Some(MessagePack::encode(42)))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn registered_function_decodes_and_responds() {
+        let mut stream = greeter()
+            .call(
+                "greet".to_string(),
+                Some(MessagePack::encode(Params {
+                    name: "World".to_string(),
+                })),
+            )
+            .await
+            .unwrap();
+
+        let response: String = stream.next().await.unwrap().unwrap().decode().unwrap();
+
+        assert_eq!(response, "Hello, World!");
+    }
+}
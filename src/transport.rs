@@ -0,0 +1,83 @@
+use std::{net::SocketAddr, path::PathBuf, pin::Pin};
+
+use futures::Stream;
+use tokio::net::{TcpListener, TcpStream, UnixListener, UnixStream};
+use tonic::transport::server::Connected;
+use tokio_stream::wrappers::{TcpListenerStream, UnixListenerStream};
+
+use crate::stdio_service::StdioService;
+
+/// A pluggable source of incoming connections that a [`crate::Module`] can be served
+/// over. The crate ships [`StdioTransport`], [`TcpTransport`] and [`UnixTransport`] for
+/// the common cases; implement this trait to serve a module over anything else.
+#[crate::async_trait]
+pub trait Transport {
+    type Conn: tokio::io::AsyncRead + tokio::io::AsyncWrite + Connected + Unpin + Send + 'static;
+
+    async fn incoming(
+        self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Self::Conn, std::io::Error>> + Send>>, anyhow::Error>;
+}
+
+/// Communicates with a single parent process over stdin/stdout. This is the default
+/// transport used by [`crate::Module::start`] and ties the module's lifetime to that one
+/// process's pipes.
+pub struct StdioTransport;
+
+#[crate::async_trait]
+impl Transport for StdioTransport {
+    type Conn = StdioService;
+
+    async fn incoming(
+        self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Self::Conn, std::io::Error>> + Send>>, anyhow::Error>
+    {
+        let stdio = StdioService {
+            stdin: tokio::io::stdin(),
+            stdout: tokio::io::stdout(),
+        };
+
+        Ok(Box::pin(tokio_stream::once(Ok(stdio))))
+    }
+}
+
+/// Accepts connections on a TCP listener, letting a module run as a standalone daemon
+/// reachable by multiple clients (local debugging, integration tests, out-of-process
+/// supervision) rather than being tied to a single parent process.
+pub struct TcpTransport {
+    pub addr: SocketAddr,
+}
+
+#[crate::async_trait]
+impl Transport for TcpTransport {
+    type Conn = TcpStream;
+
+    async fn incoming(
+        self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Self::Conn, std::io::Error>> + Send>>, anyhow::Error>
+    {
+        let listener = TcpListener::bind(self.addr).await?;
+
+        Ok(Box::pin(TcpListenerStream::new(listener)))
+    }
+}
+
+/// Accepts connections on a Unix domain socket, the same multi-client use case as
+/// [`TcpTransport`] without going through TCP.
+pub struct UnixTransport {
+    pub path: PathBuf,
+}
+
+#[crate::async_trait]
+impl Transport for UnixTransport {
+    type Conn = UnixStream;
+
+    async fn incoming(
+        self,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Self::Conn, std::io::Error>> + Send>>, anyhow::Error>
+    {
+        let listener = UnixListener::bind(&self.path)?;
+
+        Ok(Box::pin(UnixListenerStream::new(listener)))
+    }
+}